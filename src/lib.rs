@@ -141,15 +141,24 @@ impl zed::Extension for UnicodeExtension {
             .map(|lsp_settings| lsp_settings.settings)
             .unwrap_or_default()
             .unwrap_or(serde_json::json!({
-                "include_all_symbols": false
+                "include_all_symbols": false,
+                "include_category_theory": false
             }));
 
-        let args = settings
-            .get("include_all_symbols")
-            .and_then(|x| x.as_bool())
-            .filter(|x| *x)
-            .map(|_| vec!["--include_all_symbols".into()])
-            .unwrap_or_default();
+        let enabled = |key: &str| {
+            settings
+                .get(key)
+                .and_then(|x| x.as_bool())
+                .unwrap_or_default()
+        };
+
+        let mut args = vec![];
+        if enabled("include_all_symbols") {
+            args.push("--include-all-symbols".into());
+        }
+        if enabled("include_category_theory") {
+            args.push("--include-category-theory".into());
+        }
 
         Ok(Command {
             args,
@@ -0,0 +1,89 @@
+//! Code actions offered on a non-empty selection.
+
+use crate::{case, digits, emphasis, table};
+
+pub struct Action {
+    pub title: &'static str,
+    /// Cheap check run on every selection change, before offering the action.
+    pub applies: fn(&str) -> bool,
+    /// Rewrites the selected text, or returns `None` when it doesn't apply.
+    pub apply: fn(&str) -> Option<String>,
+}
+
+pub const ACTIONS: &[Action] = &[
+    Action {
+        title: "Render table with box-drawing characters",
+        applies: table::is_table,
+        apply: table::to_box,
+    },
+    Action {
+        title: "Convert box-drawing table to pipes",
+        applies: table::is_box_table,
+        apply: table::to_pipes,
+    },
+    Action {
+        title: "Convert Markdown emphasis to Unicode bold/italic",
+        applies: emphasis::has_emphasis,
+        apply: emphasis::to_unicode,
+    },
+    Action {
+        title: "Strip Unicode bold/italic styling",
+        applies: emphasis::is_styled,
+        apply: emphasis::strip,
+    },
+    Action {
+        title: "Uppercase selection (Unicode-aware)",
+        applies: case::has_lowercase,
+        apply: case::upper,
+    },
+    Action {
+        title: "Lowercase selection (Unicode-aware)",
+        applies: case::has_uppercase,
+        apply: case::lower,
+    },
+    Action {
+        title: "Titlecase selection (Unicode-aware)",
+        applies: case::has_letters,
+        apply: case::title,
+    },
+    Action {
+        title: "Uppercase selection (Turkish/Azerbaijani)",
+        applies: case::has_dotted_i,
+        apply: case::upper_turkish,
+    },
+    Action {
+        title: "Lowercase selection (Turkish/Azerbaijani)",
+        applies: case::has_dotted_i,
+        apply: case::lower_turkish,
+    },
+    Action {
+        title: "Titlecase selection (Turkish/Azerbaijani)",
+        applies: case::has_dotted_i,
+        apply: case::title_turkish,
+    },
+    Action {
+        title: "Convert digits to Western",
+        applies: digits::has_non_western,
+        apply: digits::to_western,
+    },
+    Action {
+        title: "Convert digits to Arabic-Indic",
+        applies: digits::has_non_arabic_indic,
+        apply: digits::to_arabic_indic,
+    },
+    Action {
+        title: "Convert digits to Extended Arabic-Indic",
+        applies: digits::has_non_extended_arabic_indic,
+        apply: digits::to_extended_arabic_indic,
+    },
+    Action {
+        title: "Convert digits to Devanagari",
+        applies: digits::has_non_devanagari,
+        apply: digits::to_devanagari,
+    },
+    Action {
+        title: "Convert digits to fullwidth",
+        applies: digits::has_non_fullwidth,
+        apply: digits::to_fullwidth,
+    },
+];
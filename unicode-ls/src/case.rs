@@ -0,0 +1,292 @@
+//! Case conversion with the full Unicode mappings (`ß` → `SS`, final sigma)
+//! and the Turkish and Azerbaijani rules for dotted and dotless i.
+
+use unicode_normalization::char::is_combining_mark;
+
+/// Characters whose titlecase, from the UnicodeData and SpecialCasing
+/// titlecase columns, differs from their full uppercase mapping.
+const TITLECASE: &[(char, &str)] = &[
+    ('\u{df}', "Ss"),
+    ('\u{1c4}', "\u{1c5}"),
+    ('\u{1c5}', "\u{1c5}"),
+    ('\u{1c6}', "\u{1c5}"),
+    ('\u{1c7}', "\u{1c8}"),
+    ('\u{1c8}', "\u{1c8}"),
+    ('\u{1c9}', "\u{1c8}"),
+    ('\u{1ca}', "\u{1cb}"),
+    ('\u{1cb}', "\u{1cb}"),
+    ('\u{1cc}', "\u{1cb}"),
+    ('\u{1f1}', "\u{1f2}"),
+    ('\u{1f2}', "\u{1f2}"),
+    ('\u{1f3}', "\u{1f2}"),
+    ('\u{587}', "\u{535}\u{582}"),
+    ('\u{10d0}', "\u{10d0}"),
+    ('\u{10d1}', "\u{10d1}"),
+    ('\u{10d2}', "\u{10d2}"),
+    ('\u{10d3}', "\u{10d3}"),
+    ('\u{10d4}', "\u{10d4}"),
+    ('\u{10d5}', "\u{10d5}"),
+    ('\u{10d6}', "\u{10d6}"),
+    ('\u{10d7}', "\u{10d7}"),
+    ('\u{10d8}', "\u{10d8}"),
+    ('\u{10d9}', "\u{10d9}"),
+    ('\u{10da}', "\u{10da}"),
+    ('\u{10db}', "\u{10db}"),
+    ('\u{10dc}', "\u{10dc}"),
+    ('\u{10dd}', "\u{10dd}"),
+    ('\u{10de}', "\u{10de}"),
+    ('\u{10df}', "\u{10df}"),
+    ('\u{10e0}', "\u{10e0}"),
+    ('\u{10e1}', "\u{10e1}"),
+    ('\u{10e2}', "\u{10e2}"),
+    ('\u{10e3}', "\u{10e3}"),
+    ('\u{10e4}', "\u{10e4}"),
+    ('\u{10e5}', "\u{10e5}"),
+    ('\u{10e6}', "\u{10e6}"),
+    ('\u{10e7}', "\u{10e7}"),
+    ('\u{10e8}', "\u{10e8}"),
+    ('\u{10e9}', "\u{10e9}"),
+    ('\u{10ea}', "\u{10ea}"),
+    ('\u{10eb}', "\u{10eb}"),
+    ('\u{10ec}', "\u{10ec}"),
+    ('\u{10ed}', "\u{10ed}"),
+    ('\u{10ee}', "\u{10ee}"),
+    ('\u{10ef}', "\u{10ef}"),
+    ('\u{10f0}', "\u{10f0}"),
+    ('\u{10f1}', "\u{10f1}"),
+    ('\u{10f2}', "\u{10f2}"),
+    ('\u{10f3}', "\u{10f3}"),
+    ('\u{10f4}', "\u{10f4}"),
+    ('\u{10f5}', "\u{10f5}"),
+    ('\u{10f6}', "\u{10f6}"),
+    ('\u{10f7}', "\u{10f7}"),
+    ('\u{10f8}', "\u{10f8}"),
+    ('\u{10f9}', "\u{10f9}"),
+    ('\u{10fa}', "\u{10fa}"),
+    ('\u{10fd}', "\u{10fd}"),
+    ('\u{10fe}', "\u{10fe}"),
+    ('\u{10ff}', "\u{10ff}"),
+    ('\u{1f80}', "\u{1f88}"),
+    ('\u{1f81}', "\u{1f89}"),
+    ('\u{1f82}', "\u{1f8a}"),
+    ('\u{1f83}', "\u{1f8b}"),
+    ('\u{1f84}', "\u{1f8c}"),
+    ('\u{1f85}', "\u{1f8d}"),
+    ('\u{1f86}', "\u{1f8e}"),
+    ('\u{1f87}', "\u{1f8f}"),
+    ('\u{1f88}', "\u{1f88}"),
+    ('\u{1f89}', "\u{1f89}"),
+    ('\u{1f8a}', "\u{1f8a}"),
+    ('\u{1f8b}', "\u{1f8b}"),
+    ('\u{1f8c}', "\u{1f8c}"),
+    ('\u{1f8d}', "\u{1f8d}"),
+    ('\u{1f8e}', "\u{1f8e}"),
+    ('\u{1f8f}', "\u{1f8f}"),
+    ('\u{1f90}', "\u{1f98}"),
+    ('\u{1f91}', "\u{1f99}"),
+    ('\u{1f92}', "\u{1f9a}"),
+    ('\u{1f93}', "\u{1f9b}"),
+    ('\u{1f94}', "\u{1f9c}"),
+    ('\u{1f95}', "\u{1f9d}"),
+    ('\u{1f96}', "\u{1f9e}"),
+    ('\u{1f97}', "\u{1f9f}"),
+    ('\u{1f98}', "\u{1f98}"),
+    ('\u{1f99}', "\u{1f99}"),
+    ('\u{1f9a}', "\u{1f9a}"),
+    ('\u{1f9b}', "\u{1f9b}"),
+    ('\u{1f9c}', "\u{1f9c}"),
+    ('\u{1f9d}', "\u{1f9d}"),
+    ('\u{1f9e}', "\u{1f9e}"),
+    ('\u{1f9f}', "\u{1f9f}"),
+    ('\u{1fa0}', "\u{1fa8}"),
+    ('\u{1fa1}', "\u{1fa9}"),
+    ('\u{1fa2}', "\u{1faa}"),
+    ('\u{1fa3}', "\u{1fab}"),
+    ('\u{1fa4}', "\u{1fac}"),
+    ('\u{1fa5}', "\u{1fad}"),
+    ('\u{1fa6}', "\u{1fae}"),
+    ('\u{1fa7}', "\u{1faf}"),
+    ('\u{1fa8}', "\u{1fa8}"),
+    ('\u{1fa9}', "\u{1fa9}"),
+    ('\u{1faa}', "\u{1faa}"),
+    ('\u{1fab}', "\u{1fab}"),
+    ('\u{1fac}', "\u{1fac}"),
+    ('\u{1fad}', "\u{1fad}"),
+    ('\u{1fae}', "\u{1fae}"),
+    ('\u{1faf}', "\u{1faf}"),
+    ('\u{1fb2}', "\u{1fba}\u{345}"),
+    ('\u{1fb3}', "\u{1fbc}"),
+    ('\u{1fb4}', "\u{386}\u{345}"),
+    ('\u{1fb7}', "\u{391}\u{342}\u{345}"),
+    ('\u{1fbc}', "\u{1fbc}"),
+    ('\u{1fc2}', "\u{1fca}\u{345}"),
+    ('\u{1fc3}', "\u{1fcc}"),
+    ('\u{1fc4}', "\u{389}\u{345}"),
+    ('\u{1fc7}', "\u{397}\u{342}\u{345}"),
+    ('\u{1fcc}', "\u{1fcc}"),
+    ('\u{1ff2}', "\u{1ffa}\u{345}"),
+    ('\u{1ff3}', "\u{1ffc}"),
+    ('\u{1ff4}', "\u{38f}\u{345}"),
+    ('\u{1ff7}', "\u{3a9}\u{342}\u{345}"),
+    ('\u{1ffc}', "\u{1ffc}"),
+    ('\u{fb00}', "Ff"),
+    ('\u{fb01}', "Fi"),
+    ('\u{fb02}', "Fl"),
+    ('\u{fb03}', "Ffi"),
+    ('\u{fb04}', "Ffl"),
+    ('\u{fb05}', "St"),
+    ('\u{fb06}', "St"),
+    ('\u{fb13}', "\u{544}\u{576}"),
+    ('\u{fb14}', "\u{544}\u{565}"),
+    ('\u{fb15}', "\u{544}\u{56b}"),
+    ('\u{fb16}', "\u{54e}\u{576}"),
+    ('\u{fb17}', "\u{544}\u{56d}"),
+];
+
+#[derive(Clone, Copy)]
+enum Locale {
+    Default,
+    Turkish,
+}
+
+fn to_upper(text: &str, locale: Locale) -> String {
+    match locale {
+        Locale::Default => text.to_uppercase(),
+        Locale::Turkish => text.replace('i', "İ").to_uppercase(),
+    }
+}
+
+fn to_lower(text: &str, locale: Locale) -> String {
+    match locale {
+        Locale::Default => text.to_lowercase(),
+        Locale::Turkish => text
+            .replace("I\u{307}", "i")
+            .replace('I', "ı")
+            .replace('İ', "i")
+            .to_lowercase(),
+    }
+}
+
+fn to_title_char(c: char, locale: Locale) -> String {
+    match TITLECASE.binary_search_by_key(&c, |(c, _)| *c) {
+        Ok(i) => TITLECASE[i].1.to_string(),
+        Err(_) => to_upper(&c.to_string(), locale),
+    }
+}
+
+/// Combining marks belong to the letter before them, so decomposed text is
+/// titlecased like the composed one.
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || is_combining_mark(c) || c == '\'' || c == '’'
+}
+
+fn to_title(text: &str, locale: Locale) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let start = rest.find(char::is_alphanumeric).unwrap_or(rest.len());
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = rest.find(|c| !is_word(c)).unwrap_or(rest.len());
+        let mut word = rest[..end].chars();
+        if let Some(first) = word.next() {
+            result.push_str(&to_title_char(first, locale));
+            result.push_str(&to_lower(word.as_str(), locale));
+        }
+        rest = &rest[end..];
+    }
+
+    result
+}
+
+/// Only offers the Turkish conversion when it differs from the default one.
+fn turkish(text: &str, convert: fn(&str, Locale) -> String) -> Option<String> {
+    let converted = convert(text, Locale::Turkish);
+    (converted != convert(text, Locale::Default)).then_some(converted)
+}
+
+pub fn has_lowercase(text: &str) -> bool {
+    text.chars().any(char::is_lowercase)
+}
+
+pub fn has_uppercase(text: &str) -> bool {
+    text.chars().any(char::is_uppercase)
+}
+
+pub fn has_letters(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic)
+}
+
+/// Whether the Turkish rules could change anything, which only differ from
+/// the default ones on `i` and `I`.
+pub fn has_dotted_i(text: &str) -> bool {
+    text.contains(['i', 'I', 'İ'])
+}
+
+pub fn upper(text: &str) -> Option<String> {
+    Some(to_upper(text, Locale::Default))
+}
+
+pub fn lower(text: &str) -> Option<String> {
+    Some(to_lower(text, Locale::Default))
+}
+
+pub fn title(text: &str) -> Option<String> {
+    Some(to_title(text, Locale::Default))
+}
+
+pub fn upper_turkish(text: &str) -> Option<String> {
+    turkish(text, to_upper)
+}
+
+pub fn lower_turkish(text: &str) -> Option<String> {
+    turkish(text, to_lower)
+}
+
+pub fn title_turkish(text: &str) -> Option<String> {
+    turkish(text, to_title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_full_case_mappings() {
+        assert_eq!(upper("straße").as_deref(), Some("STRASSE"));
+        assert_eq!(lower("ΟΔΟΣ ΚΑΙ").as_deref(), Some("οδος και"));
+        assert_eq!(lower("ΟΔΟΣ").as_deref(), Some("οδος"));
+    }
+
+    #[test]
+    fn titlecases_words() {
+        assert_eq!(
+            title("hello wORLD, don't 2nd").as_deref(),
+            Some("Hello World, Don't 2nd")
+        );
+        assert_eq!(title("ßa ﬁsh").as_deref(), Some("Ssa Fish"));
+        assert_eq!(title("ǆungla ǉ").as_deref(), Some("ǅungla ǈ"));
+        assert_eq!(title("ᾳb ῃ ῳ ᾀ").as_deref(), Some("ᾼb ῌ ῼ ᾈ"));
+        assert_eq!(title("ŉ").as_deref(), Some("ʼN"));
+        assert_eq!(
+            title("e\u{301}cole noe\u{308}l").as_deref(),
+            Some("E\u{301}cole Noe\u{308}l")
+        );
+    }
+
+    #[test]
+    fn titlecase_table_is_sorted() {
+        assert!(TITLECASE.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn applies_turkish_rules() {
+        assert_eq!(upper_turkish("istanbul").as_deref(), Some("İSTANBUL"));
+        assert_eq!(lower_turkish("IRMAK İZMİR").as_deref(), Some("ırmak izmir"));
+        assert_eq!(title_turkish("izmir").as_deref(), Some("İzmir"));
+        assert_eq!(upper_turkish("HELLO"), None);
+        assert!(!has_dotted_i("ßa"));
+    }
+}
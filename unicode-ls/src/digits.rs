@@ -0,0 +1,128 @@
+//! Conversion between the decimal digits of different scripts.
+
+const WESTERN: u32 = 0x30;
+const ARABIC_INDIC: u32 = 0x660;
+const EXTENDED_ARABIC_INDIC: u32 = 0x6F0;
+const DEVANAGARI: u32 = 0x966;
+const FULLWIDTH: u32 = 0xFF10;
+
+const SYSTEMS: &[u32] = &[
+    WESTERN,
+    ARABIC_INDIC,
+    EXTENDED_ARABIC_INDIC,
+    DEVANAGARI,
+    FULLWIDTH,
+];
+
+fn value(c: char) -> Option<u32> {
+    SYSTEMS
+        .iter()
+        .find_map(|zero| (c as u32).checked_sub(*zero).filter(|d| *d < 10))
+}
+
+/// Whether the text has a digit from a system other than the one starting
+/// at `zero`, that is one converting to it would change.
+fn has_other(text: &str, zero: u32) -> bool {
+    text.chars()
+        .any(|c| value(c).is_some_and(|d| c as u32 != zero + d))
+}
+
+pub fn has_non_western(text: &str) -> bool {
+    has_other(text, WESTERN)
+}
+
+pub fn has_non_arabic_indic(text: &str) -> bool {
+    has_other(text, ARABIC_INDIC)
+}
+
+pub fn has_non_extended_arabic_indic(text: &str) -> bool {
+    has_other(text, EXTENDED_ARABIC_INDIC)
+}
+
+pub fn has_non_devanagari(text: &str) -> bool {
+    has_other(text, DEVANAGARI)
+}
+
+pub fn has_non_fullwidth(text: &str) -> bool {
+    has_other(text, FULLWIDTH)
+}
+
+/// Rewrites every digit of the known systems with the one starting at `zero`.
+fn convert(text: &str, zero: u32) -> Option<String> {
+    let mut converted = false;
+    let result = text
+        .chars()
+        .map(|c| match value(c).and_then(|d| char::from_u32(zero + d)) {
+            Some(digit) if digit != c => {
+                converted = true;
+                digit
+            }
+            _ => c,
+        })
+        .collect();
+
+    converted.then_some(result)
+}
+
+pub fn to_western(text: &str) -> Option<String> {
+    convert(text, WESTERN)
+}
+
+pub fn to_arabic_indic(text: &str) -> Option<String> {
+    convert(text, ARABIC_INDIC)
+}
+
+pub fn to_extended_arabic_indic(text: &str) -> Option<String> {
+    convert(text, EXTENDED_ARABIC_INDIC)
+}
+
+pub fn to_devanagari(text: &str) -> Option<String> {
+    convert(text, DEVANAGARI)
+}
+
+pub fn to_fullwidth(text: &str) -> Option<String> {
+    convert(text, FULLWIDTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIXED: &str = "2024 ٢٠٢٤ ۲۰۲۴ २०२४ ２０２４";
+
+    #[test]
+    fn converts_between_systems() {
+        assert_eq!(
+            to_western(MIXED).as_deref(),
+            Some("2024 2024 2024 2024 2024")
+        );
+        assert_eq!(
+            to_arabic_indic(MIXED).as_deref(),
+            Some("٢٠٢٤ ٢٠٢٤ ٢٠٢٤ ٢٠٢٤ ٢٠٢٤")
+        );
+        assert_eq!(
+            to_extended_arabic_indic(MIXED).as_deref(),
+            Some("۲۰۲۴ ۲۰۲۴ ۲۰۲۴ ۲۰۲۴ ۲۰۲۴")
+        );
+        assert_eq!(
+            to_devanagari(MIXED).as_deref(),
+            Some("२०२४ २०२४ २०२४ २०२४ २०२४")
+        );
+        assert_eq!(
+            to_fullwidth(MIXED).as_deref(),
+            Some("２０２４ ２０２４ ２０２４ ２０２４ ２０２４")
+        );
+    }
+
+    #[test]
+    fn skips_text_without_other_digits() {
+        assert_eq!(to_western("12 ab"), None);
+        assert!(!has_non_western("abc 12"));
+        assert!(has_non_western("x٣"));
+        assert!(!has_non_arabic_indic("x٣"));
+        assert!(has_non_arabic_indic("x3"));
+        assert!(!has_non_devanagari("२०२४"));
+        assert!(has_non_fullwidth("２0"));
+        assert!(!has_non_extended_arabic_indic("۲۰"));
+    }
+}
@@ -0,0 +1,151 @@
+//! Text of the documents open in the editor, kept in sync with
+//! `textDocument/didChange` to resolve the selections of code actions.
+
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    /// Counted in the code units of the negotiated [`Encoding`].
+    pub character: usize,
+}
+
+/// The `positionEncoding` picked by the server, UTF-16 unless it says
+/// otherwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    #[default]
+    Utf16,
+    Utf32,
+}
+
+impl Encoding {
+    pub fn from_kind(kind: &str) -> Self {
+        match kind {
+            "utf-8" => Self::Utf8,
+            "utf-32" => Self::Utf32,
+            _ => Self::Utf16,
+        }
+    }
+
+    fn len(self, c: char) -> usize {
+        match self {
+            Self::Utf8 => c.len_utf8(),
+            Self::Utf16 => c.len_utf16(),
+            Self::Utf32 => 1,
+        }
+    }
+}
+
+/// Byte offset of a position. Positions past the end of a line or of the
+/// text are clamped to it, and the ones inside a character move past it.
+pub fn offset(text: &str, position: Position, encoding: Encoding) -> usize {
+    let start = match position.line {
+        0 => 0,
+        line => match text.match_indices('\n').nth(line - 1) {
+            Some((i, _)) => i + 1,
+            None => return text.len(),
+        },
+    };
+
+    let mut units = 0;
+    for (i, c) in text[start..].char_indices() {
+        if units >= position.character || c == '\n' {
+            return start + i;
+        }
+        units += encoding.len(c);
+    }
+
+    text.len()
+}
+
+pub fn range(text: &str, start: Position, end: Position, encoding: Encoding) -> Range<usize> {
+    let start = offset(text, start, encoding);
+    start..offset(text, end, encoding).max(start)
+}
+
+/// Applies one of the `contentChanges`, which replaces the whole text when it
+/// has no range.
+pub fn apply_change(
+    text: &mut String,
+    range: Option<(Position, Position)>,
+    new_text: &str,
+    encoding: Encoding,
+) {
+    match range {
+        Some((start, end)) => {
+            let range = self::range(text, start, end, encoding);
+            text.replace_range(range, new_text);
+        }
+        None => *text = new_text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line: usize, character: usize) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn counts_utf16_units() {
+        let text = "a😀b\nc";
+        assert_eq!(offset(text, at(0, 0), Encoding::Utf16), 0);
+        assert_eq!(offset(text, at(0, 1), Encoding::Utf16), 1);
+        assert_eq!(offset(text, at(0, 3), Encoding::Utf16), 5);
+        assert_eq!(offset(text, at(0, 4), Encoding::Utf16), 6);
+        assert_eq!(offset(text, at(1, 1), Encoding::Utf16), 8);
+    }
+
+    #[test]
+    fn counts_utf8_and_utf32_units() {
+        let text = "aé😀b";
+        assert_eq!(offset(text, at(0, 3), Encoding::Utf8), 3);
+        assert_eq!(offset(text, at(0, 7), Encoding::Utf8), 7);
+        assert_eq!(offset(text, at(0, 2), Encoding::Utf32), 3);
+        assert_eq!(offset(text, at(0, 3), Encoding::Utf32), 7);
+        assert_eq!(Encoding::from_kind("utf-8"), Encoding::Utf8);
+        assert_eq!(Encoding::from_kind("utf-32"), Encoding::Utf32);
+        assert_eq!(Encoding::from_kind("utf-16"), Encoding::Utf16);
+    }
+
+    #[test]
+    fn moves_past_surrogate_pairs() {
+        assert_eq!(offset("a😀b", at(0, 2), Encoding::Utf16), 5);
+    }
+
+    #[test]
+    fn clamps_past_the_end() {
+        let text = "ab\ncd\n";
+        assert_eq!(offset(text, at(0, 10), Encoding::Utf16), 2);
+        assert_eq!(offset(text, at(1, 10), Encoding::Utf16), 5);
+        assert_eq!(offset(text, at(2, 0), Encoding::Utf16), 6);
+        assert_eq!(offset(text, at(7, 3), Encoding::Utf16), 6);
+        assert_eq!(range(text, at(1, 1), at(0, 1), Encoding::Utf16), 4..4);
+    }
+
+    #[test]
+    fn applies_changes_in_order() {
+        let mut text = "let x = 1;\nlet y = 2;\n".to_string();
+        let changes = [
+            (Some((at(0, 4), at(0, 5))), "α"),
+            (Some((at(1, 8), at(1, 9))), "😀"),
+            (Some((at(1, 10), at(1, 11))), "!"),
+            (Some((at(2, 0), at(2, 0))), "// end\n"),
+        ];
+        for (range, new_text) in changes {
+            apply_change(&mut text, range, new_text, Encoding::Utf16);
+        }
+        assert_eq!(text, "let α = 1;\nlet y = 😀!\n// end\n");
+    }
+
+    #[test]
+    fn replaces_whole_text_without_range() {
+        let mut text = "old".to_string();
+        apply_change(&mut text, None, "new\ntext", Encoding::Utf16);
+        assert_eq!(text, "new\ntext");
+    }
+}
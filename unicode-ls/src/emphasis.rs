@@ -0,0 +1,270 @@
+//! Markdown emphasis rendered with the sans-serif letters from the
+//! Mathematical Alphanumeric Symbols block, for places without markup.
+
+#[derive(Clone, Copy)]
+enum Style {
+    Italic,
+    Bold,
+    BoldItalic,
+}
+
+/// Greek letters in the order of each style of the Mathematical Alphanumeric
+/// Symbols block.
+const GREEK: &str = "ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡϴΣΤΥΦΧΨΩ∇αβγδεζηθικλμνξοπρςστυφχψω∂ϵϑϰϕϱϖ";
+
+fn styled(c: char, style: Style) -> char {
+    let (upper, lower) = match style {
+        Style::Italic => (0x1D608, 0x1D622),
+        Style::Bold => (0x1D5D4, 0x1D5EE),
+        Style::BoldItalic => (0x1D63C, 0x1D656),
+    };
+
+    let code = match c {
+        'A'..='Z' => upper + (c as u32 - 'A' as u32),
+        'a'..='z' => lower + (c as u32 - 'a' as u32),
+        // There are no italic digits, the bold ones are used for both.
+        '0'..='9' if !matches!(style, Style::Italic) => 0x1D7EC + (c as u32 - '0' as u32),
+        _ => return c,
+    };
+
+    char::from_u32(code).unwrap_or(c)
+}
+
+fn plain(c: char) -> char {
+    let code = c as u32;
+    match code {
+        0x1D400..=0x1D6A3 => {
+            let i = ((code - 0x1D400) % 52) as u8;
+            match i {
+                0..=25 => char::from(b'A' + i),
+                _ => char::from(b'a' + i - 26),
+            }
+        }
+        0x1D6A8..=0x1D7C9 => GREEK
+            .chars()
+            .nth(((code - 0x1D6A8) % 58) as usize)
+            .unwrap_or(c),
+        0x1D7CA => 'Ϝ',
+        0x1D7CB => 'ϝ',
+        0x1D7CE..=0x1D7FF => char::from(b'0' + ((code - 0x1D7CE) % 10) as u8),
+        _ => match c {
+            'ℎ' => 'h',
+            'ℬ' => 'B',
+            'ℰ' => 'E',
+            'ℱ' => 'F',
+            'ℋ' | 'ℌ' | 'ℍ' => 'H',
+            'ℐ' | 'ℑ' => 'I',
+            'ℒ' => 'L',
+            'ℳ' => 'M',
+            'ℛ' | 'ℜ' | 'ℝ' => 'R',
+            'ℯ' => 'e',
+            'ℊ' => 'g',
+            'ℴ' => 'o',
+            'ℭ' | 'ℂ' => 'C',
+            'ℨ' | 'ℤ' => 'Z',
+            'ℕ' => 'N',
+            'ℙ' => 'P',
+            'ℚ' => 'Q',
+            _ => c,
+        },
+    }
+}
+
+/// Finds the end of a code span opened by the backtick run at `start`, which
+/// is closed by a run of the same length.
+fn code_span(chars: &[char], start: usize) -> Option<usize> {
+    let run = chars[start..].iter().take_while(|c| **c == '`').count();
+
+    let mut i = start + run;
+    while i < chars.len() {
+        let len = chars[i..].iter().take_while(|c| **c == '`').count();
+        if len == run {
+            return Some(i + len);
+        }
+        i += len.max(1);
+    }
+
+    None
+}
+
+/// Finds the delimiter run closing an emphasis opened right before `start`.
+fn closing(chars: &[char], start: usize, delimiter: char, run: usize) -> Option<usize> {
+    if chars.get(start).is_none_or(|c| c.is_whitespace()) {
+        return None;
+    }
+
+    let mut i = start;
+    while i < chars.len() {
+        if chars[i] == '`' {
+            if let Some(end) = code_span(chars, i) {
+                i = end;
+                continue;
+            }
+        }
+
+        if chars[i] != delimiter {
+            // Emphasis doesn't span paragraphs.
+            if chars[i] == '\n' && chars.get(i + 1) == Some(&'\n') {
+                return None;
+            }
+            i += 1;
+            continue;
+        }
+
+        let len = chars[i..].iter().take_while(|c| **c == delimiter).count();
+        let intraword = delimiter == '_' && chars.get(i + len).is_some_and(|c| c.is_alphanumeric());
+        if len == run && !chars[i - 1].is_whitespace() && !intraword {
+            return Some(i);
+        }
+        i += len;
+    }
+
+    None
+}
+
+pub fn has_emphasis(text: &str) -> bool {
+    text.contains(['*', '_'])
+}
+
+pub fn is_styled(text: &str) -> bool {
+    text.chars().any(|c| plain(c) != c)
+}
+
+/// Style of a span nested inside another one, `*italic*` inside `**bold**`
+/// ends up both bold and italic.
+fn nest(outer: Style, inner: Style) -> Style {
+    match (outer, inner) {
+        (Style::Italic, Style::Italic) => Style::Italic,
+        (Style::Bold, Style::Bold) => Style::Bold,
+        _ => Style::BoldItalic,
+    }
+}
+
+/// Renders `chars` with the `outer` style, converting the emphasis spans in
+/// it. Returns whether any span was converted.
+fn render(chars: &[char], outer: Option<Style>, result: &mut String) -> bool {
+    let mut converted = false;
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        // Code spans are copied as they are, delimiters included.
+        if c == '`' {
+            let end = code_span(chars, i)
+                .unwrap_or(i + chars[i..].iter().take_while(|c| **c == '`').count());
+            result.extend(&chars[i..end]);
+            i = end;
+            continue;
+        }
+
+        if c != '*' && c != '_' {
+            result.push(outer.map_or(c, |style| styled(c, style)));
+            i += 1;
+            continue;
+        }
+
+        let run = chars[i..].iter().take_while(|d| **d == c).count();
+        let intraword = c == '_' && i > 0 && chars[i - 1].is_alphanumeric();
+        let end = match run {
+            1..=3 if !intraword => closing(chars, i + run, c, run),
+            _ => None,
+        };
+
+        if let Some(end) = end {
+            let style = match run {
+                1 => Style::Italic,
+                2 => Style::Bold,
+                _ => Style::BoldItalic,
+            };
+            let style = outer.map_or(style, |outer| nest(outer, style));
+            let span = &chars[i + run..end];
+
+            let mut rendered = String::new();
+            render(span, Some(style), &mut rendered);
+
+            // Spans without anything to style, like `*3*` in `2*3*4`, are not
+            // emphasis worth dropping the delimiters for.
+            let mut unstyled = String::new();
+            render(span, outer, &mut unstyled);
+            if rendered == unstyled {
+                result.extend(&chars[i..i + run]);
+                result.push_str(&unstyled);
+                result.extend(&chars[end..end + run]);
+            } else {
+                result.push_str(&rendered);
+                converted = true;
+            }
+
+            i = end + run;
+            continue;
+        }
+
+        result.extend(&chars[i..i + run]);
+        i += run;
+    }
+
+    converted
+}
+
+/// Replaces `*italic*`, `**bold**` and `***bold italic***` (or the underscore
+/// forms) with styled letters, dropping the delimiters.
+pub fn to_unicode(text: &str) -> Option<String> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    render(&chars, None, &mut result).then_some(result)
+}
+
+/// Turns styled mathematical letters and digits back into plain ones.
+pub fn strip(text: &str) -> Option<String> {
+    Some(text.chars().map(plain).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_emphasis() {
+        assert_eq!(
+            to_unicode("**bold** and *italic* and ___both___ 42").as_deref(),
+            Some("𝗯𝗼𝗹𝗱 and 𝘪𝘵𝘢𝘭𝘪𝘤 and 𝙗𝙤𝙩𝙝 42")
+        );
+        assert_eq!(to_unicode("**2024**").as_deref(), Some("𝟮𝟬𝟮𝟰"));
+    }
+
+    #[test]
+    fn converts_nested_emphasis() {
+        assert_eq!(to_unicode("**bold *it* x**").as_deref(), Some("𝗯𝗼𝗹𝗱 𝙞𝙩 𝘅"));
+        assert_eq!(to_unicode("*a **b** c*").as_deref(), Some("𝘢 𝙗 𝘤"));
+    }
+
+    #[test]
+    fn keeps_spans_without_styled_characters() {
+        assert_eq!(to_unicode("2*3*4"), None);
+        assert_eq!(to_unicode("2*3*4 *x*").as_deref(), Some("2*3*4 𝘹"));
+    }
+
+    #[test]
+    fn skips_code_spans() {
+        assert_eq!(to_unicode("`a*b*c`"), None);
+        assert_eq!(to_unicode("``a `*b*` c``"), None);
+        assert_eq!(to_unicode("*x `y*` z*").as_deref(), Some("𝘹 `y*` 𝘻"));
+        assert_eq!(to_unicode("`*a* *b*").as_deref(), Some("`𝘢 𝘣"));
+    }
+
+    #[test]
+    fn ignores_intraword_underscores() {
+        assert_eq!(to_unicode("snake_case_name"), None);
+        assert_eq!(to_unicode("a * b * c"), None);
+    }
+
+    #[test]
+    fn strips_styling() {
+        assert_eq!(
+            strip("𝗯𝗼𝗹𝗱 𝘪𝘵𝘢𝘭𝘪𝘤 𝙗𝙤𝙩𝙝 𝟮𝟬 𝛂 ℕ").as_deref(),
+            Some("bold italic both 20 α N")
+        );
+        assert!(is_styled("𝘹"));
+        assert!(!is_styled("plain"));
+    }
+}
@@ -4,16 +4,53 @@ use clap::Parser;
 use simple_completion_language_server::*;
 use snippets::Snippet;
 
+mod actions;
+mod case;
+mod digits;
+mod document;
+mod emphasis;
+mod proxy;
+mod table;
+
+/// Languages where the structured math templates are offered.
+const MATH_SCOPES: &[&str] = &["markdown", "asciidoc"];
+
+/// Renders a snippet body as it reads once expanded with the default
+/// placeholders, so `∑_{${1:i=1}}^{${2:n}} ${0}` shows up as `∑_{i=1}^{n}`.
+fn preview(body: &str) -> String {
+    let mut result = String::new();
+    let mut rest = body;
+    while let Some(i) = rest.find('$') {
+        result.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+
+        if let Some(tabstop) = rest.strip_prefix('{') {
+            let end = tabstop.find('}').unwrap_or(tabstop.len());
+            if let Some((_, placeholder)) = tabstop[..end].split_once(':') {
+                result.push_str(placeholder);
+            }
+            rest = tabstop.get(end + 1..).unwrap_or_default();
+        } else if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            rest = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+        } else {
+            result.push('$');
+        }
+    }
+    result.push_str(rest);
+
+    result.trim_end().to_string()
+}
+
 macro_rules! create_snippet_map {
-    ($($k:expr => $v:expr),*) => {{
+    (scope: $scope:expr; $($k:expr => $v:expr),*) => {{
         let mut v = vec![];
         let mut h = vec![];
         $(
             if !h.contains(&$k.to_string()) {
                 v.push(Snippet {
-                    scope: None,
+                    scope: $scope,
                     prefix: $k.to_string(),
-                    description: Some($v.to_string().clone()),
+                    description: Some(preview(&$v.to_string())),
                     body: $v.to_string(),
                 });
                 h.push($k.to_string());
@@ -21,6 +58,9 @@ macro_rules! create_snippet_map {
         )*
         v
     }};
+    ($($k:expr => $v:expr),*) => {
+        create_snippet_map!(scope: None; $($k => $v),*)
+    };
 }
 
 fn get_prefix(s: &str) -> Option<String> {
@@ -44,6 +84,9 @@ fn get_prefix(s: &str) -> Option<String> {
 struct Cli {
     #[arg(short, long)]
     include_all_symbols: bool,
+
+    #[arg(long)]
+    include_category_theory: bool,
 }
 
 #[tokio::main]
@@ -114,6 +157,8 @@ async fn main() {
         ":" => '⦂',
         "N" => 'ℕ',
         "C" => 'ℂ',
+        "Hilbert" => 'ℋ',
+        "hilbert" => 'ℋ',
         "delta" => 'Δ',
         "contains" => '∋',
         "not contains" => '∌',
@@ -149,6 +194,8 @@ async fn main() {
         "||" => '∥',
         "parallel" => '∥',
         "oplus" => '⊕',
+        "otimes" => '⊗',
+        "tensor" => '⊗',
         "veebar" => '⊻',
         "not equiv" => '≢',
         "!=" => '≢',
@@ -217,9 +264,50 @@ async fn main() {
         "Psi" => 'Ψ',
         "omega" => 'ω',
         "Omega" => 'Ω',
-        "->>" => '↠'
+        "->>" => '↠',
+        "nul" => '␀',
+        "tab" => '␉',
+        "lf" => '␊',
+        "space-symbol" => '␠',
+        "del" => '␡'
     };
 
+    snippets.extend(create_snippet_map! {
+        scope: Some(MATH_SCOPES.iter().map(|s| s.to_string()).collect());
+        "sum" => "∑_{${1:i=1}}^{${2:n}} ${0}",
+        "prod" => "∏_{${1:i=1}}^{${2:n}} ${0}",
+        "int" => "∫_{${1:a}}^{${2:b}} ${3:f(x)} d${4:x} ${0}",
+        "oint" => "∮_{${1:C}} ${2:f(z)} d${3:z} ${0}",
+        "lim" => "lim_{${1:x} → ${2:∞}} ${0}",
+        "bra" => "⟨${1:ψ}|",
+        "ket" => "|${1:ψ}⟩",
+        "braket" => "⟨${1:φ}|${2:ψ}⟩"
+    });
+
+    if cli.include_category_theory {
+        snippets.extend(create_snippet_map! {
+            "rightarrowtail" => '↣',
+            "mono" => '↣',
+            ">->" => '↣',
+            "twoheadrightarrow" => '↠',
+            "epi" => '↠',
+            "rightrightarrows" => '⇉',
+            "parallel pair" => '⇉',
+            "rightleftarrows" => '⇄',
+            "cong" => '≅',
+            "iso" => '≅',
+            "simeq" => '≃',
+            "equivalence" => '≃',
+            "dashv" => '⊣',
+            "adjoint" => '⊣',
+            "-|" => '⊣',
+            "circ" => '∘',
+            "compose" => '∘',
+            "2-cell" => '⇒',
+            "nat" => '⇒'
+        });
+    }
+
     dbg!(cli.include_all_symbols);
 
     if cli.include_all_symbols {
@@ -269,12 +357,42 @@ async fn main() {
 
     dbg!(&all_snippets);
 
-    server::start(
-        stdin,
-        stdout,
-        all_snippets,
-        HashMap::new(),
-        etcetera::home_dir().unwrap().to_str().unwrap().into(),
-    )
-    .await;
+    let (client, completion) = tokio::io::duplex(1 << 16);
+    let (completion_read, completion_write) = tokio::io::split(completion);
+
+    tokio::join!(
+        server::start(
+            completion_read,
+            completion_write,
+            all_snippets,
+            HashMap::new(),
+            etcetera::home_dir().unwrap().to_str().unwrap().into(),
+        ),
+        proxy::start(stdin, stdout, client),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_renders_placeholders() {
+        assert_eq!(preview("∑_{${1:i=1}}^{${2:n}} ${0}"), "∑_{i=1}^{n}");
+        assert_eq!(
+            preview("∫_{${1:a}}^{${2:b}} ${3:f(x)} d${4:x} ${0}"),
+            "∫_{a}^{b} f(x) dx"
+        );
+        assert_eq!(
+            preview("∮_{${1:C}} ${2:f(z)} d${3:z} ${0}"),
+            "∮_{C} f(z) dz"
+        );
+        assert_eq!(preview("lim_{${1:x} → ${2:∞}} $0"), "lim_{x → ∞}");
+    }
+
+    #[test]
+    fn preview_keeps_plain_bodies() {
+        assert_eq!(preview("→"), "→");
+        assert_eq!(preview("$"), "$");
+    }
 }
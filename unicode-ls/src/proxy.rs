@@ -0,0 +1,418 @@
+//! LSP layer in front of the completion server.
+//!
+//! `simple-completion-language-server` only knows about completions, so every
+//! message from the editor passes through here first: open documents are
+//! mirrored to resolve selections, `textDocument/codeAction` is answered
+//! locally, and everything else is forwarded untouched.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use serde_json::{json, Map, Value};
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tokio::sync::Mutex;
+
+use crate::actions::{Action, ACTIONS};
+use crate::document::{self, Encoding, Position};
+
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> Option<Vec<u8>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await.ok()? == 0 {
+            return None;
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let mut body = vec![0; length?];
+    reader.read_exact(&mut body).await.ok()?;
+    Some(body)
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(writer: &mut W, body: &[u8]) -> std::io::Result<()> {
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(body).await?;
+    writer.flush().await
+}
+
+fn position(value: &Value) -> Option<Position> {
+    Some(Position {
+        line: usize::try_from(value["line"].as_u64()?).ok()?,
+        character: usize::try_from(value["character"].as_u64()?).ok()?,
+    })
+}
+
+fn range(text: &str, range: &Value, encoding: Encoding) -> Option<Range<usize>> {
+    let start = position(&range["start"])?;
+    let end = position(&range["end"])?;
+    Some(document::range(text, start, end, encoding))
+}
+
+fn apply_change(text: &mut String, change: &Value, encoding: Encoding) {
+    let Some(new_text) = change["text"].as_str() else {
+        return;
+    };
+
+    let range = position(&change["range"]["start"]).zip(position(&change["range"]["end"]));
+    document::apply_change(text, range, new_text, encoding);
+}
+
+/// The only kind of action offered, rewriting the selection in place.
+const KIND: &str = "refactor.rewrite";
+
+/// Whether the client's `context.only` filter lets rewrites through.
+fn requested(params: &Value) -> bool {
+    let Some(only) = params["context"]["only"].as_array() else {
+        return true;
+    };
+
+    only.iter()
+        .filter_map(Value::as_str)
+        .any(|kind| KIND == kind || KIND.starts_with(&format!("{kind}.")))
+}
+
+fn selection<'a>(
+    documents: &'a HashMap<String, String>,
+    uri: &str,
+    selected: &Value,
+    encoding: Encoding,
+) -> Option<&'a str> {
+    let text = documents.get(uri)?;
+    let selected = range(text, selected, encoding).filter(|range| !range.is_empty())?;
+    Some(&text[selected])
+}
+
+fn edit(uri: &str, range: &Value, new_text: String) -> Value {
+    let mut changes = Map::new();
+    changes.insert(
+        uri.to_string(),
+        json!([{ "range": range, "newText": new_text }]),
+    );
+
+    json!({ "changes": changes })
+}
+
+/// Rewrites the selection with an action, or returns `None` when it changes
+/// nothing, so that picking it from the menu always does something.
+fn rewrite(action: &Action, selection: &str) -> Option<String> {
+    if !(action.applies)(selection) {
+        return None;
+    }
+    (action.apply)(selection).filter(|new_text| new_text != selection)
+}
+
+/// Lists the actions that change the selection. When the client can resolve
+/// them, the edits are left out and only sent back in `codeAction/resolve`.
+fn code_actions(
+    documents: &HashMap<String, String>,
+    params: &Value,
+    lazy: bool,
+    encoding: Encoding,
+) -> Value {
+    if !requested(params) {
+        return json!([]);
+    }
+
+    let Some(uri) = params["textDocument"]["uri"].as_str() else {
+        return json!([]);
+    };
+    let Some(selection) = selection(documents, uri, &params["range"], encoding) else {
+        return json!([]);
+    };
+
+    let actions = ACTIONS
+        .iter()
+        .enumerate()
+        .filter_map(|(i, action)| {
+            let new_text = rewrite(action, selection)?;
+
+            let mut code_action = json!({
+                "title": action.title,
+                "kind": KIND,
+                "data": { "uri": uri, "range": params["range"], "action": i },
+            });
+            if !lazy {
+                code_action["edit"] = edit(uri, &params["range"], new_text);
+            }
+            Some(code_action)
+        })
+        .collect();
+
+    Value::Array(actions)
+}
+
+fn resolved_edit(
+    documents: &HashMap<String, String>,
+    data: &Value,
+    encoding: Encoding,
+) -> Option<Value> {
+    let uri = data["uri"].as_str()?;
+    let action = ACTIONS.get(usize::try_from(data["action"].as_u64()?).ok()?)?;
+    let selection = selection(documents, uri, &data["range"], encoding)?;
+    Some(edit(uri, &data["range"], rewrite(action, selection)?))
+}
+
+fn resolve(documents: &HashMap<String, String>, code_action: &Value, encoding: Encoding) -> Value {
+    let mut code_action = code_action.clone();
+    if let Some(edit) = resolved_edit(documents, &code_action["data"], encoding) {
+        code_action["edit"] = edit;
+    }
+    code_action
+}
+
+/// Whether the client resolves the edits of code actions on its own request,
+/// from the `initialize` params.
+fn resolves_edits(params: &Value) -> bool {
+    params["capabilities"]["textDocument"]["codeAction"]["resolveSupport"]["properties"]
+        .as_array()
+        .is_some_and(|properties| {
+            properties
+                .iter()
+                .any(|property| property.as_str() == Some("edit"))
+        })
+}
+
+pub async fn start<I, O, S>(stdin: I, stdout: O, server: S)
+where
+    I: AsyncRead + Unpin,
+    O: AsyncWrite + Unpin,
+    S: AsyncRead + AsyncWrite,
+{
+    let (server_read, mut server_write) = tokio::io::split(server);
+    let stdout = &Mutex::new(stdout);
+    // Set from the server's `initialize` response, before any document opens.
+    let encoding = &OnceLock::new();
+
+    let client = async move {
+        let mut documents = HashMap::<String, String>::new();
+        let mut lazy = false;
+        let mut reader = BufReader::new(stdin);
+
+        while let Some(body) = read_message(&mut reader).await {
+            let message = serde_json::from_slice::<Value>(&body).unwrap_or_default();
+            let params = &message["params"];
+            let encoding = encoding.get().copied().unwrap_or_default();
+
+            let result = match message["method"].as_str() {
+                Some("textDocument/codeAction") => {
+                    Some(code_actions(&documents, params, lazy, encoding))
+                }
+                Some("codeAction/resolve") => Some(resolve(&documents, params, encoding)),
+                _ => None,
+            };
+
+            if let Some(result) = result {
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "id": message["id"],
+                    "result": result,
+                });
+
+                let mut stdout = stdout.lock().await;
+                if write_message(&mut *stdout, response.to_string().as_bytes())
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+                continue;
+            }
+
+            match message["method"].as_str() {
+                Some("initialize") => lazy = resolves_edits(params),
+                Some("textDocument/didOpen") => {
+                    let document = &params["textDocument"];
+                    if let (Some(uri), Some(text)) =
+                        (document["uri"].as_str(), document["text"].as_str())
+                    {
+                        documents.insert(uri.to_string(), text.to_string());
+                    }
+                }
+                Some("textDocument/didChange") => {
+                    let text = params["textDocument"]["uri"]
+                        .as_str()
+                        .and_then(|uri| documents.get_mut(uri));
+                    if let Some(text) = text {
+                        for change in params["contentChanges"].as_array().into_iter().flatten() {
+                            apply_change(text, change, encoding);
+                        }
+                    }
+                }
+                Some("textDocument/didClose") => {
+                    if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                        documents.remove(uri);
+                    }
+                }
+                _ => {}
+            }
+
+            if write_message(&mut server_write, &body).await.is_err() {
+                break;
+            }
+        }
+
+        server_write.shutdown().await.ok();
+    };
+
+    let server = async move {
+        let mut reader = BufReader::new(server_read);
+
+        while let Some(mut body) = read_message(&mut reader).await {
+            // Advertise the code actions in the `initialize` response, and note
+            // how it counts positions.
+            if let Ok(mut message) = serde_json::from_slice::<Value>(&body) {
+                if let Some(capabilities) = message
+                    .pointer_mut("/result/capabilities")
+                    .and_then(Value::as_object_mut)
+                {
+                    let kind = capabilities.get("positionEncoding").and_then(Value::as_str);
+                    encoding
+                        .set(kind.map(Encoding::from_kind).unwrap_or_default())
+                        .ok();
+                    capabilities.insert(
+                        "codeActionProvider".into(),
+                        json!({ "codeActionKinds": [KIND], "resolveProvider": true }),
+                    );
+                    body = message.to_string().into_bytes();
+                }
+            }
+
+            let mut stdout = stdout.lock().await;
+            if write_message(&mut *stdout, &body).await.is_err() {
+                break;
+            }
+        }
+    };
+
+    tokio::join!(client, server);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn documents() -> HashMap<String, String> {
+        HashMap::from([("file:///a.md".to_string(), "x\ta\ny\tb\n".to_string())])
+    }
+
+    fn params(only: Value) -> Value {
+        json!({
+            "textDocument": { "uri": "file:///a.md" },
+            "range": {
+                "start": { "line": 0, "character": 0 },
+                "end": { "line": 2, "character": 0 },
+            },
+            "context": { "diagnostics": [], "only": only },
+        })
+    }
+
+    #[test]
+    fn filters_by_requested_kind() {
+        assert!(requested(&json!({})));
+        assert!(requested(&params(json!(["refactor"]))));
+        assert!(requested(&params(json!(["quickfix", "refactor.rewrite"]))));
+        assert!(!requested(&params(json!(["quickfix"]))));
+        assert!(!requested(&params(json!(["refactor.extract"]))));
+        assert_eq!(
+            code_actions(
+                &documents(),
+                &params(json!(["quickfix"])),
+                false,
+                Encoding::Utf16
+            ),
+            json!([])
+        );
+    }
+
+    #[test]
+    fn resolves_edits_lazily() {
+        let documents = documents();
+        let actions = code_actions(&documents, &params(Value::Null), true, Encoding::Utf16);
+        let actions = actions.as_array().unwrap();
+        assert!(actions.iter().all(|action| action.get("edit").is_none()));
+
+        let table = actions
+            .iter()
+            .find(|action| action["title"] == "Render table with box-drawing characters")
+            .unwrap();
+        let resolved = resolve(&documents, table, Encoding::Utf16);
+        let edit = &resolved["edit"]["changes"]["file:///a.md"][0];
+        assert_eq!(edit["range"], params(Value::Null)["range"]);
+        assert!(edit["newText"].as_str().unwrap().starts_with("┌───┬───┐"));
+    }
+
+    #[test]
+    fn includes_edits_without_resolve_support() {
+        let actions = code_actions(&documents(), &params(Value::Null), false, Encoding::Utf16);
+        let actions = actions.as_array().unwrap();
+        assert!(!actions.is_empty());
+        assert!(actions.iter().all(|action| action["edit"].is_object()));
+
+        assert!(resolves_edits(&json!({
+            "capabilities": { "textDocument": { "codeAction": {
+                "resolveSupport": { "properties": ["edit"] },
+            } } },
+        })));
+        assert!(!resolves_edits(&json!({ "capabilities": {} })));
+    }
+
+    #[test]
+    fn only_offers_actions_that_change_the_selection() {
+        // Already rendered, so only converting it to pipes changes anything.
+        let table = "┌───┬───┐\n│ a │ b │\n└───┴───┘\n";
+        let documents = HashMap::from([("file:///a.md".to_string(), table.to_string())]);
+        let params = json!({
+            "textDocument": { "uri": "file:///a.md" },
+            "range": {
+                "start": { "line": 0, "character": 0 },
+                "end": { "line": 3, "character": 0 },
+            },
+        });
+
+        let actions = code_actions(&documents, &params, false, Encoding::Utf16);
+        let titles = actions
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|action| action["title"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert!(titles.contains(&"Convert box-drawing table to pipes"));
+        assert!(!titles.contains(&"Render table with box-drawing characters"));
+    }
+
+    #[test]
+    fn mirrors_changes() {
+        let mut text = "a😀b".to_string();
+        apply_change(
+            &mut text,
+            &json!({
+                "range": {
+                    "start": { "line": 0, "character": 3 },
+                    "end": { "line": 0, "character": 4 },
+                },
+                "text": "c",
+            }),
+            Encoding::Utf16,
+        );
+        assert_eq!(text, "a😀c");
+
+        apply_change(&mut text, &json!({ "text": "full" }), Encoding::Utf16);
+        assert_eq!(text, "full");
+    }
+}
@@ -0,0 +1,349 @@
+//! Tables drawn with box-drawing characters.
+//!
+//! Rows may be tab separated, Markdown pipe tables or box-drawing tables, so
+//! rendering an already drawn table just re-aligns it.
+
+use unicode_width::UnicodeWidthStr;
+
+type Rows = Vec<Vec<String>>;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Box,
+    Pipes,
+    Tabs,
+}
+
+struct Table {
+    indent: String,
+    format: Format,
+    rows: Rows,
+}
+
+/// Horizontal rules of a box-drawing table, which carry no cells.
+fn is_rule(line: &str) -> bool {
+    line.starts_with(['┌', '├', '└']) && line.chars().all(|c| "─┌┬┐├┼┤└┴┘ ".contains(c))
+}
+
+/// Cells of the Markdown delimiter row, like `---` or `:-:`.
+fn is_delimiter(cell: &str) -> bool {
+    let cell = cell.strip_prefix(':').unwrap_or(cell);
+    let cell = cell.strip_suffix(':').unwrap_or(cell);
+    !cell.is_empty() && cell.chars().all(|c| c == '-')
+}
+
+fn split_box(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('│').unwrap_or(line);
+    let line = line.strip_suffix('│').unwrap_or(line);
+    line.split('│')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+fn split_pipes(line: &str) -> Vec<String> {
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell);
+
+    // The outer pipes are optional in Markdown, and leave empty cells behind.
+    if line.starts_with('|') {
+        cells.remove(0);
+    }
+    if line.ends_with('|') && !line.ends_with("\\|") {
+        cells.pop();
+    }
+
+    cells
+        .into_iter()
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+/// Reads a selection as a table, or returns `None` unless every row has the
+/// same number of columns. Plain text needs at least two rows to count, and
+/// pipe tables without outer pipes need the delimiter row below the header.
+///
+/// A leading tab in tab-separated values is an empty cell, as in the corner of
+/// a pivot table, so only spaces indent them, and a column that is empty all
+/// the way down is read as code indented with tabs instead.
+fn parse(text: &str) -> Option<Table> {
+    let lines = text
+        .lines()
+        .map(|line| line.trim_end_matches([' ', '\r']))
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>();
+
+    let first = lines.first()?;
+    let format = if first.contains('│') || is_rule(first.trim_start()) {
+        Format::Box
+    } else if first.contains('|') {
+        Format::Pipes
+    } else if first.contains('\t') {
+        Format::Tabs
+    } else {
+        return None;
+    };
+    let indent = match format {
+        Format::Tabs => &first[..first.len() - first.trim_start_matches(' ').len()],
+        _ => &first[..first.len() - first.trim_start_matches([' ', '\t']).len()],
+    };
+
+    let mut rows = vec![];
+    let mut delimited = false;
+    for line in lines {
+        let line = line.strip_prefix(indent).unwrap_or(line.trim_start());
+        let row = match format {
+            Format::Box if is_rule(line) => continue,
+            Format::Box => split_box(line),
+            Format::Pipes => split_pipes(line),
+            Format::Tabs => line
+                .split('\t')
+                .map(|cell| cell.trim().to_string())
+                .collect(),
+        };
+
+        if format == Format::Pipes
+            && rows.len() == 1
+            && !delimited
+            && row.iter().all(|cell| is_delimiter(cell))
+        {
+            delimited = true;
+            continue;
+        }
+        rows.push(row);
+    }
+
+    let columns = rows.first()?.len();
+    if columns < 2 || rows.iter().any(|row| row.len() != columns) {
+        return None;
+    }
+
+    let table = match format {
+        Format::Box => true,
+        Format::Pipes => rows.len() >= 2 && (delimited || text.trim_start().starts_with('|')),
+        Format::Tabs => {
+            rows.len() >= 2 && (0..columns).all(|i| rows.iter().any(|row| !row[i].is_empty()))
+        }
+    };
+
+    table.then(|| Table {
+        indent: indent.to_string(),
+        format,
+        rows,
+    })
+}
+
+fn widths(rows: &Rows) -> Vec<usize> {
+    (0..rows[0].len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].width())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn pad(row: &[String], widths: &[usize]) -> Vec<String> {
+    row.iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - cell.width())))
+        .collect()
+}
+
+fn finish(text: &str, lines: Vec<String>) -> String {
+    let mut table = lines.join("\n");
+    if text.ends_with('\n') {
+        table.push('\n');
+    }
+    table
+}
+
+pub fn is_table(text: &str) -> bool {
+    parse(text).is_some()
+}
+
+pub fn is_box_table(text: &str) -> bool {
+    parse(text).is_some_and(|table| table.format == Format::Box)
+}
+
+/// Renders a table with box-drawing characters, with a rule below the header.
+pub fn to_box(text: &str) -> Option<String> {
+    let Table { indent, rows, .. } = parse(text)?;
+    let widths = widths(&rows);
+
+    let rule = |left: char, middle: &str, right: char| {
+        let cells = widths
+            .iter()
+            .map(|width| "─".repeat(width + 2))
+            .collect::<Vec<_>>();
+        format!("{indent}{left}{}{right}", cells.join(middle))
+    };
+
+    let mut lines = vec![rule('┌', "┬", '┐')];
+    for (i, row) in rows.iter().enumerate() {
+        if i == 1 {
+            lines.push(rule('├', "┼", '┤'));
+        }
+        lines.push(format!("{indent}│ {} │", pad(row, &widths).join(" │ ")));
+    }
+    lines.push(rule('└', "┴", '┘'));
+
+    Some(finish(text, lines))
+}
+
+/// Turns a box-drawing table back into a Markdown pipe table.
+pub fn to_pipes(text: &str) -> Option<String> {
+    let Table { indent, rows, .. } = parse(text).filter(|table| table.format == Format::Box)?;
+    let rows = rows
+        .into_iter()
+        .map(|row| row.iter().map(|cell| cell.replace('|', "\\|")).collect())
+        .collect::<Rows>();
+    let widths = widths(&rows)
+        .into_iter()
+        .map(|width| width.max(3))
+        .collect::<Vec<_>>();
+
+    let dashes = widths
+        .iter()
+        .map(|width| "-".repeat(*width))
+        .collect::<Vec<_>>();
+
+    let mut lines = vec![];
+    for (i, row) in rows.iter().enumerate() {
+        lines.push(format!("{indent}| {} |", pad(row, &widths).join(" | ")));
+        // Markdown needs the delimiter row even when there is only a header.
+        if i == 0 {
+            lines.push(format!("{indent}| {} |", dashes.join(" | ")));
+        }
+    }
+
+    Some(finish(text, lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_tab_separated_values() {
+        assert_eq!(
+            to_box("name\tage\nalice\t30\n").as_deref(),
+            Some(concat!(
+                "┌───────┬─────┐\n",
+                "│ name  │ age │\n",
+                "├───────┼─────┤\n",
+                "│ alice │ 30  │\n",
+                "└───────┴─────┘\n",
+            ))
+        );
+    }
+
+    #[test]
+    fn keeps_rows_that_look_like_rules() {
+        let table = to_box("a\tb\n-\t-\nc\td\n").unwrap();
+        assert_eq!(
+            table.lines().filter(|line| line.starts_with('│')).count(),
+            3
+        );
+
+        let table = to_box("| a | b |\n| --- | --- |\n| - | : |\n").unwrap();
+        assert!(table.contains("│ - │ : │"));
+    }
+
+    #[test]
+    fn realigns_box_tables() {
+        let table = "┌─┬─┐\n│ long │ x │\n├─┼─┤\n│ y │ z │\n└─┴─┘";
+        assert_eq!(
+            to_box(table).as_deref(),
+            Some(concat!(
+                "┌──────┬───┐\n",
+                "│ long │ x │\n",
+                "├──────┼───┤\n",
+                "│ y    │ z │\n",
+                "└──────┴───┘",
+            ))
+        );
+    }
+
+    #[test]
+    fn aligns_wide_characters() {
+        let table = to_box("名前\tx\nab\ty\n１２\tz").unwrap();
+        let widths = table
+            .lines()
+            .map(UnicodeWidthStr::width)
+            .collect::<Vec<_>>();
+        assert!(widths.iter().all(|width| *width == widths[0]));
+        assert!(table.contains("│ ab   │ y │"));
+    }
+
+    #[test]
+    fn keeps_indentation() {
+        let table = to_box("  a\tb\n  c\td").unwrap();
+        assert!(table.lines().all(|line| line.starts_with("  ")));
+        assert!(table.contains("│ a │ b │"));
+    }
+
+    #[test]
+    fn keeps_the_corner_of_pivot_tables() {
+        assert_eq!(
+            to_box("\tQ1\tQ2\nnorth\t1\t2\nsouth\t3\t4\n").as_deref(),
+            Some(concat!(
+                "┌───────┬────┬────┐\n",
+                "│       │ Q1 │ Q2 │\n",
+                "├───────┼────┼────┤\n",
+                "│ north │ 1  │ 2  │\n",
+                "│ south │ 3  │ 4  │\n",
+                "└───────┴────┴────┘\n",
+            ))
+        );
+    }
+
+    #[test]
+    fn converts_single_row_to_pipes() {
+        assert_eq!(
+            to_pipes("┌───┬───┐\n│ a │ b │\n└───┴───┘").as_deref(),
+            Some("| a   | b   |\n| --- | --- |")
+        );
+    }
+
+    #[test]
+    fn escapes_pipes_in_cells() {
+        let pipes = to_pipes("│ a|b │ c │\n│ d │ e │").unwrap();
+        assert_eq!(pipes.lines().next(), Some("| a\\|b | c   |"));
+        assert!(to_box(&pipes).unwrap().contains("│ a|b │ c │"));
+    }
+
+    #[test]
+    fn round_trips_through_box() {
+        let pipes = concat!(
+            "| name  | age  |\n",
+            "| ----- | ---- |\n",
+            "| alice | 30   |\n",
+            "| 名前  | x\\|y |\n",
+        );
+        let table = to_box(pipes).unwrap();
+        assert_eq!(to_pipes(&table).as_deref(), Some(pipes));
+        assert_eq!(to_box(&to_pipes(&table).unwrap()), Some(table));
+    }
+
+    #[test]
+    fn ignores_code() {
+        assert_eq!(to_box("\tfoo();\n\tbar();"), None);
+        assert_eq!(to_box("if a || b {"), None);
+        assert_eq!(to_box("if a || b {\n    return;\n}"), None);
+        assert_eq!(to_box("let x = a | b;\nlet y = c | d;"), None);
+        assert_eq!(to_box("a\tb\nc"), None);
+    }
+}